@@ -0,0 +1,91 @@
+//! Async counterpart to [`SourceReader::reader`](crate::SourceReader::reader),
+//! available behind the `async` feature.
+
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_util::TryStreamExt;
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+use tokio_util::io::StreamReader;
+
+use crate::SourceReader;
+
+/// Adapts an in-memory buffer into an [`AsyncRead`], for [`SourceReader::Data`]
+struct CursorReader(io::Cursor<Vec<u8>>);
+
+impl AsyncRead for CursorReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let read = io::Read::read(&mut self.get_mut().0, buf.initialize_unfilled())?;
+        buf.advance(read);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl SourceReader {
+    /// Creates an async reader for the file path
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - An optional reqwest client to use if the path is remote
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn run() -> std::io::Result<()> {
+    /// use source_reader::SourceReader;
+    /// let file = SourceReader::from("/path/to/file");
+    /// let mut reader = file.async_reader(None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn async_reader(
+        &self,
+        client: Option<reqwest::Client>,
+    ) -> io::Result<Pin<Box<dyn AsyncRead + Send>>> {
+        match self {
+            SourceReader::Local(path) => {
+                let file = tokio::fs::File::open(path).await?;
+                Ok(Box::pin(file))
+            }
+            SourceReader::Remote(url) => {
+                let client = client.unwrap_or_default();
+                let response = client
+                    .get(url)
+                    .send()
+                    .await
+                    .map_err(io::Error::other)?
+                    .error_for_status()
+                    .map_err(io::Error::other)?;
+
+                let stream = response.bytes_stream().map_err(io::Error::other);
+
+                Ok(Box::pin(StreamReader::new(stream)))
+            }
+            SourceReader::Stdin => Ok(Box::pin(tokio::io::stdin())),
+            SourceReader::Data(bytes) => Ok(Box::pin(CursorReader(io::Cursor::new(bytes.clone())))),
+            SourceReader::Custom(uri) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("no async reader available for custom scheme URI {uri}"),
+            )),
+        }
+    }
+
+    /// Convenience method to asynchronously read all data at once
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - An optional reqwest client to use if the path is remote
+    pub async fn read_to_end_async(&self, client: Option<reqwest::Client>) -> io::Result<Vec<u8>> {
+        let mut reader = self.async_reader(client).await?;
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        Ok(buf)
+    }
+}
@@ -0,0 +1,208 @@
+//! Digest verification for bytes pulled from a [`crate::SourceReader`].
+
+use std::io::{Error, ErrorKind, Read, Result};
+
+use sha2::{Digest as _, Sha256, Sha512};
+
+/// The hash algorithm used to compute a [`Digest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+}
+
+/// An expected hash value, checked against the bytes read from a source
+///
+/// # Examples
+///
+/// ```
+/// use source_reader::{Algorithm, Digest};
+/// let digest = Digest::new(Algorithm::Sha256, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digest {
+    algorithm: Algorithm,
+    hex: String,
+}
+
+impl Digest {
+    /// Creates a new `Digest` from an algorithm and an expected hex digest
+    pub fn new(algorithm: Algorithm, hex: impl Into<String>) -> Self {
+        Digest {
+            algorithm,
+            hex: hex.into().to_ascii_lowercase(),
+        }
+    }
+
+    fn hasher(&self) -> Hasher {
+        match self.algorithm {
+            Algorithm::Sha256 => Hasher::Sha256(Box::new(Sha256::new())),
+            Algorithm::Sha512 => Hasher::Sha512(Box::new(Sha512::new())),
+        }
+    }
+
+    fn matches(&self, computed: &str) -> bool {
+        computed.eq_ignore_ascii_case(&self.hex)
+    }
+}
+
+enum Hasher {
+    Sha256(Box<Sha256>),
+    Sha512(Box<Sha512>),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    fn hex_digest(self) -> String {
+        match self {
+            Hasher::Sha256(hasher) => hex::encode(hasher.finalize()),
+            Hasher::Sha512(hasher) => hex::encode(hasher.finalize()),
+        }
+    }
+}
+
+/// Returns an [`io::Error`](std::io::Error) describing a digest mismatch
+pub(crate) fn mismatch_error(expected: &Digest, computed: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!(
+            "digest mismatch: expected {:?} {}, computed {}",
+            expected.algorithm, expected.hex, computed
+        ),
+    )
+}
+
+/// Streams through `reader`, hashing every chunk, and returns the read bytes
+/// if the computed digest matches `expected`
+pub(crate) fn read_verified(mut reader: impl Read, expected: &Digest) -> Result<Vec<u8>> {
+    let mut hasher = expected.hasher();
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+
+        hasher.update(&chunk[..read]);
+        buf.extend_from_slice(&chunk[..read]);
+    }
+
+    let computed = hasher.hex_digest();
+    if expected.matches(&computed) {
+        Ok(buf)
+    } else {
+        Err(mismatch_error(expected, &computed))
+    }
+}
+
+/// A [`Read`] wrapper that hashes bytes as they are read and checks the
+/// digest against an expected value once the underlying reader hits EOF
+///
+/// This lets a caller verify large files without buffering the entire
+/// contents in memory first.
+pub struct VerifyingReader<R: Read> {
+    inner: R,
+    hasher: Hasher,
+    expected: Digest,
+    done: bool,
+}
+
+impl<R: Read> VerifyingReader<R> {
+    /// Wraps `inner`, hashing bytes as they are read against `expected`
+    pub fn new(inner: R, expected: Digest) -> Self {
+        let hasher = expected.hasher();
+
+        VerifyingReader {
+            inner,
+            hasher,
+            expected,
+            done: false,
+        }
+    }
+}
+
+impl<R: Read> Read for VerifyingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // A zero-length read of an empty buffer is not EOF.
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let read = self.inner.read(buf)?;
+
+        if read == 0 {
+            if !self.done {
+                self.done = true;
+
+                // Replace the hasher so `hex_digest` can consume it without
+                // moving out of `self` through a `&mut` reference.
+                let hasher = std::mem::replace(&mut self.hasher, self.expected.hasher());
+                let computed = hasher.hex_digest();
+
+                if !self.expected.matches(&computed) {
+                    return Err(mismatch_error(&self.expected, &computed));
+                }
+            }
+        } else {
+            self.hasher.update(&buf[..read]);
+        }
+
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn read_verified_accepts_matching_digest() {
+        let expected = Digest::new(
+            Algorithm::Sha256,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+        );
+
+        let data = read_verified(Cursor::new(b"hello".to_vec()), &expected).unwrap();
+        assert_eq!(data, b"hello");
+    }
+
+    #[test]
+    fn read_verified_rejects_mismatched_digest() {
+        let expected = Digest::new(Algorithm::Sha256, "not-the-real-digest");
+
+        let err = read_verified(Cursor::new(b"hello".to_vec()), &expected).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn verifying_reader_errors_on_mismatch_at_eof() {
+        let expected = Digest::new(Algorithm::Sha256, "not-the-real-digest");
+        let mut reader = VerifyingReader::new(Cursor::new(b"hello".to_vec()), expected);
+
+        let mut buf = Vec::new();
+        let err = reader.read_to_end(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn verifying_reader_passes_through_matching_data() {
+        let expected = Digest::new(
+            Algorithm::Sha256,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+        );
+        let mut reader = VerifyingReader::new(Cursor::new(b"hello".to_vec()), expected);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, b"hello");
+    }
+}
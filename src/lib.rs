@@ -5,7 +5,20 @@ use std::{
 };
 use ureq::Agent;
 
-/// Represents a file path that can be local, remote, or the standard input
+#[cfg(feature = "async")]
+mod async_reader;
+mod digest;
+mod mime;
+mod policy;
+mod scheme;
+mod sink;
+
+pub use digest::{Algorithm, Digest, VerifyingReader};
+pub use policy::ReaderOptions;
+pub use scheme::SchemeHandler;
+pub use sink::{FinishWrite, SourceSink};
+
+/// Represents a file path that can be local, remote, inline, or the standard input
 #[derive(Debug, Clone)]
 pub enum SourceReader {
     /// A local file path
@@ -14,6 +27,10 @@ pub enum SourceReader {
     Remote(String),
     /// Standard input (stdin)
     Stdin,
+    /// Inline content decoded from a `data:` URI (RFC 2397)
+    Data(Vec<u8>),
+    /// A URI handled by a scheme registered with [`SourceReader::register_scheme`]
+    Custom(String),
 }
 
 impl From<&str> for SourceReader {
@@ -21,12 +38,28 @@ impl From<&str> for SourceReader {
     ///
     /// - **Stdin:** If the string is `-`
     /// - **Remote:** If the string begins with `http://` or `https://`
-    /// - **Local Path:** If the string didn't match with Stdin or Remote
+    /// - **Local Path:** If the string begins with `file://`, or if nothing
+    ///   else matched
+    /// - **Data:** If the string begins with `data:` and decodes successfully
+    /// - **Custom:** If the string begins with a scheme registered via
+    ///   [`SourceReader::register_scheme`]
     fn from(path: &str) -> Self {
         if path == "-" {
             SourceReader::Stdin
         } else if path.starts_with("http://") || path.starts_with("https://") {
             SourceReader::Remote(path.to_string())
+        } else if path.starts_with("file://") {
+            match scheme::parse_file_url(path) {
+                Some(local_path) => SourceReader::Local(local_path),
+                None => SourceReader::Local(PathBuf::from(path)),
+            }
+        } else if path.starts_with("data:") {
+            match scheme::parse_data_uri(path) {
+                Ok(bytes) => SourceReader::Data(bytes),
+                Err(_) => SourceReader::Local(PathBuf::from(path)),
+            }
+        } else if scheme::is_registered(path) {
+            SourceReader::Custom(path.to_string())
         } else {
             SourceReader::Local(PathBuf::from(path))
         }
@@ -37,7 +70,9 @@ impl From<&str> for SourceReader {
 ///
 /// - **Stdin:** If the string is `-`
 /// - **Remote:** If the string begins with `http://` or `https://`
-/// - **Local Path:** If the string didn't match with Stdin or Remote
+/// - **Local Path:** If the string begins with `file://`, or if nothing else matched
+/// - **Data:** If the string begins with `data:` and decodes successfully
+/// - **Custom:** If the string begins with a registered scheme
 impl From<String> for SourceReader {
     fn from(path: String) -> Self {
         SourceReader::from(path.as_str())
@@ -95,9 +130,21 @@ impl SourceReader {
                 Ok(Box::new(body.into_reader()))
             }
             SourceReader::Stdin => Ok(Box::new(io::stdin())),
+            SourceReader::Data(bytes) => Ok(Box::new(io::Cursor::new(bytes.clone()))),
+            SourceReader::Custom(uri) => scheme::open(uri),
         }
     }
 
+    /// Registers a handler for URIs starting with `prefix` (e.g. `"s3://"`)
+    ///
+    /// Once registered, [`SourceReader::from`] recognizes matching URIs as
+    /// [`SourceReader::Custom`] and dispatches to `handler` when `reader()`
+    /// or `read_to_end()` is called, so downstream crates can wire in
+    /// custom transports without this crate depending on them.
+    pub fn register_scheme(prefix: &str, handler: impl SchemeHandler + 'static) {
+        scheme::register(prefix, handler);
+    }
+
     /// Convenience method to read all data at once
     ///
     /// # Arguments
@@ -114,12 +161,114 @@ impl SourceReader {
     /// ```
     #[cfg(not(doctest))]
     pub fn read_to_end(&self, agent: Option<Agent>) -> Result<Vec<u8>> {
-        let mut reader = self.reader(agent)?;
+        match self {
+            SourceReader::Local(path) => {
+                let mut file = File::open(path)?;
+                let capacity = file.metadata().map(|meta| meta.len() as usize).unwrap_or(0);
+                let mut buf = Vec::with_capacity(capacity);
+                file.read_to_end(&mut buf)?;
+                Ok(buf)
+            }
+            SourceReader::Remote(url) => {
+                let agent = agent.unwrap_or_else(Self::default_agent);
+                let res = agent.get(url).call().map_err(Error::other)?;
+
+                let capacity = res
+                    .headers()
+                    .get("content-length")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .unwrap_or(0);
+
+                let mut buf = Vec::with_capacity(capacity);
+                res.into_body().into_reader().read_to_end(&mut buf)?;
+                Ok(buf)
+            }
+            SourceReader::Stdin => {
+                let mut buf = Vec::new();
+                io::stdin().read_to_end(&mut buf)?;
+                Ok(buf)
+            }
+            SourceReader::Data(bytes) => Ok(bytes.clone()),
+            SourceReader::Custom(uri) => {
+                let mut buf = Vec::new();
+                scheme::open(uri)?.read_to_end(&mut buf)?;
+                Ok(buf)
+            }
+        }
+    }
+
+    /// Creates a reader for the file path, enforcing a [`ReaderOptions`] host policy
+    ///
+    /// Returns `Err` before any request is made if `options` blocks the
+    /// target host. Only `Remote` sources are subject to the policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - An optional ureq agent to use if the path is remote
+    /// * `options` - The host allow/deny policy to enforce
+    pub fn reader_with_options(
+        &self,
+        agent: Option<Agent>,
+        options: &ReaderOptions,
+    ) -> Result<Box<dyn Read>> {
+        if let SourceReader::Remote(url) = self {
+            options.check(url)?;
+        }
+
+        self.reader(agent)
+    }
+
+    /// Convenience method to read all data at once, enforcing a [`ReaderOptions`] host policy
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - An optional ureq agent to use if the path is remote
+    /// * `options` - The host allow/deny policy to enforce
+    pub fn read_to_end_with_options(
+        &self,
+        agent: Option<Agent>,
+        options: &ReaderOptions,
+    ) -> Result<Vec<u8>> {
+        let mut reader = self.reader_with_options(agent, options)?;
         let mut buf = Vec::new();
         reader.read_to_end(&mut buf)?;
         Ok(buf)
     }
 
+    /// Reads all data at once, verifying it against an expected [`Digest`]
+    ///
+    /// Returns `Err` if the computed digest of the bytes does not match
+    /// `expected`. Useful for remote artifacts fetched over the network,
+    /// where the response should not be trusted until its hash is checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - An optional ureq agent to use if the path is remote
+    /// * `expected` - The digest the bytes must match
+    pub fn read_verified(&self, agent: Option<Agent>, expected: &Digest) -> Result<Vec<u8>> {
+        let reader = self.reader(agent)?;
+        digest::read_verified(reader, expected)
+    }
+
+    /// Reads all data at once, enforcing a [`ReaderOptions`] host policy and
+    /// verifying it against an expected [`Digest`]
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - An optional ureq agent to use if the path is remote
+    /// * `options` - The host allow/deny policy to enforce
+    /// * `expected` - The digest the bytes must match
+    pub fn read_verified_with_options(
+        &self,
+        agent: Option<Agent>,
+        options: &ReaderOptions,
+        expected: &Digest,
+    ) -> Result<Vec<u8>> {
+        let reader = self.reader_with_options(agent, options)?;
+        digest::read_verified(reader, expected)
+    }
+
     /// Returns the filename of the source, will return `None` for stdin
     ///
     /// # Examples
@@ -145,8 +294,85 @@ impl SourceReader {
             }
             SourceReader::Remote(url) => url.split('/').next_back().map(String::from),
             SourceReader::Stdin => None,
+            SourceReader::Data(_) => None,
+            SourceReader::Custom(uri) => uri.split('/').next_back().map(String::from),
         }
     }
+
+    /// Guesses the Content-Type of the source
+    ///
+    /// For `Local`, the type is guessed from the file extension. For
+    /// `Remote`, the `Content-Type` header of the response is preferred, and
+    /// the extension of the URL path is used as a fallback. Always returns
+    /// `None` for `Stdin`.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - An optional ureq agent to use if the path is remote
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use source_reader::SourceReader;
+    /// let file = SourceReader::from("/path/to/file.json");
+    /// assert_eq!(file.mime_type(None), Some("application/json".to_string()));
+    /// ```
+    pub fn mime_type(&self, agent: Option<Agent>) -> Option<String> {
+        match self {
+            SourceReader::Local(path) => path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(mime::guess_from_extension)
+                .map(String::from),
+            SourceReader::Remote(url) => {
+                let agent = agent.unwrap_or_else(Self::default_agent);
+
+                let header = agent.head(url).call().ok().and_then(|res| {
+                    res.headers()
+                        .get("content-type")
+                        .and_then(|value| value.to_str().ok())
+                        .map(String::from)
+                });
+
+                header.or_else(|| {
+                    url.split('/')
+                        .next_back()
+                        .and_then(|name| name.rsplit_once('.'))
+                        .and_then(|(_, ext)| mime::guess_from_extension(ext))
+                        .map(String::from)
+                })
+            }
+            SourceReader::Stdin => None,
+            SourceReader::Data(_) => None,
+            SourceReader::Custom(uri) => uri
+                .split('/')
+                .next_back()
+                .and_then(|name| name.rsplit_once('.'))
+                .and_then(|(_, ext)| mime::guess_from_extension(ext))
+                .map(String::from),
+        }
+    }
+
+    /// Guesses the Content-Type of the source, enforcing a [`ReaderOptions`] host policy
+    ///
+    /// Returns `Err` before any request is made if `options` blocks the
+    /// target host. Only `Remote` sources are subject to the policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - An optional ureq agent to use if the path is remote
+    /// * `options` - The host allow/deny policy to enforce
+    pub fn mime_type_with_options(
+        &self,
+        agent: Option<Agent>,
+        options: &ReaderOptions,
+    ) -> Result<Option<String>> {
+        if let SourceReader::Remote(url) = self {
+            options.check(url)?;
+        }
+
+        Ok(self.mime_type(agent))
+    }
 }
 
 #[cfg(test)]
@@ -176,4 +402,16 @@ mod tests {
 
         assert_eq!(data, b"Hello!");
     }
+
+    #[test]
+    fn mime_type_is_none_for_unrecognized_local_extension() {
+        let file = SourceReader::from("/path/to/file.unknownext");
+        assert_eq!(file.mime_type(None), None);
+    }
+
+    #[test]
+    fn mime_type_is_none_for_local_path_without_extension() {
+        let file = SourceReader::from("/path/to/file");
+        assert_eq!(file.mime_type(None), None);
+    }
 }
@@ -0,0 +1,58 @@
+//! Minimal extension-based MIME type sniffing, in the spirit of `mime_guess`.
+
+/// Maps a lowercased file extension (without the leading dot) to a MIME type.
+///
+/// This only covers the extensions that are common enough to be worth
+/// guessing without pulling in a full `mime_guess`-style database; anything
+/// unrecognized returns `None`.
+pub(crate) fn guess_from_extension(extension: &str) -> Option<&'static str> {
+    let mime = match extension.to_ascii_lowercase().as_str() {
+        "html" | "htm" => "text/html",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "wasm" => "application/wasm",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => return None,
+    };
+
+    Some(mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_known_extensions() {
+        assert_eq!(guess_from_extension("json"), Some("application/json"));
+        assert_eq!(guess_from_extension("png"), Some("image/png"));
+    }
+
+    #[test]
+    fn is_case_insensitive() {
+        assert_eq!(guess_from_extension("HTML"), Some("text/html"));
+        assert_eq!(guess_from_extension("Json"), Some("application/json"));
+    }
+
+    #[test]
+    fn returns_none_for_unknown_extensions() {
+        assert_eq!(guess_from_extension("unknownext"), None);
+    }
+}
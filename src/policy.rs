@@ -0,0 +1,189 @@
+//! Host allow/deny policy for [`SourceReader::Remote`](crate::SourceReader::Remote).
+
+use std::{
+    io::{Error, ErrorKind, Result},
+    net::IpAddr,
+};
+
+/// Restricts which hosts a `Remote` [`SourceReader`](crate::SourceReader) may contact
+///
+/// An empty allowlist means "any host is allowed" unless it is also denied;
+/// a non-empty allowlist means only matching hosts pass. The denylist always
+/// takes precedence over the allowlist.
+///
+/// # Examples
+///
+/// ```
+/// use source_reader::ReaderOptions;
+/// let options = ReaderOptions::new()
+///     .allow("example.com")
+///     .reject_private_ips(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReaderOptions {
+    allowlist: Vec<String>,
+    denylist: Vec<String>,
+    reject_private_ips: bool,
+}
+
+impl ReaderOptions {
+    /// Creates an empty `ReaderOptions` that allows any host
+    pub fn new() -> Self {
+        ReaderOptions::default()
+    }
+
+    /// Adds a domain to the allowlist
+    ///
+    /// A leading `*.` matches any subdomain of the domain, e.g.
+    /// `*.example.com` matches `api.example.com` but not `example.com`
+    /// itself unless that is added separately.
+    pub fn allow(mut self, domain: impl Into<String>) -> Self {
+        self.allowlist.push(domain.into());
+        self
+    }
+
+    /// Adds a domain to the denylist
+    ///
+    /// Matching rules are the same as [`allow`](Self::allow).
+    pub fn deny(mut self, domain: impl Into<String>) -> Self {
+        self.denylist.push(domain.into());
+        self
+    }
+
+    /// Toggles rejection of private/loopback IP literal hosts, e.g.
+    /// `http://127.0.0.1/` or `http://10.0.0.5/`
+    pub fn reject_private_ips(mut self, reject: bool) -> Self {
+        self.reject_private_ips = reject;
+        self
+    }
+
+    /// Returns `Err` if `url`'s host is blocked by this policy
+    pub(crate) fn check(&self, url: &str) -> Result<()> {
+        let host = host_of(url).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("could not parse host from {url}"),
+            )
+        })?;
+
+        if self.reject_private_ips {
+            if let Ok(ip) = host.parse::<IpAddr>() {
+                if is_private_or_loopback(ip) {
+                    return Err(blocked(&host));
+                }
+            }
+        }
+
+        if self
+            .denylist
+            .iter()
+            .any(|domain| domain_matches(domain, &host))
+        {
+            return Err(blocked(&host));
+        }
+
+        if !self.allowlist.is_empty()
+            && !self
+                .allowlist
+                .iter()
+                .any(|domain| domain_matches(domain, &host))
+        {
+            return Err(blocked(&host));
+        }
+
+        Ok(())
+    }
+}
+
+fn blocked(host: &str) -> Error {
+    Error::new(
+        ErrorKind::PermissionDenied,
+        format!("host {host} is blocked by policy"),
+    )
+}
+
+/// Extracts the host (without port or userinfo) from a `http(s)://` URL
+fn host_of(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let authority = authority
+        .rsplit_once('@')
+        .map(|(_, h)| h)
+        .unwrap_or(authority);
+
+    if let Some(stripped) = authority.strip_prefix('[') {
+        // IPv6 literal, e.g. `[::1]:8080`
+        return stripped.split(']').next().map(String::from);
+    }
+
+    let host = authority.split(':').next().unwrap_or(authority);
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_ascii_lowercase())
+    }
+}
+
+/// Matches `host` against a domain pattern, supporting a `*.` subdomain wildcard
+fn domain_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host
+            .strip_suffix(suffix)
+            .is_some_and(|rest| rest.ends_with('.')),
+        None => pattern.eq_ignore_ascii_case(host),
+    }
+}
+
+fn is_private_or_loopback(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_private() || ip.is_loopback() || ip.is_link_local(),
+        IpAddr::V6(ip) => ip.is_loopback() || (ip.segments()[0] & 0xfe00) == 0xfc00,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_matches_subdomains_only_at_a_label_boundary() {
+        assert!(domain_matches("*.example.com", "api.example.com"));
+        assert!(!domain_matches("*.example.com", "evilexample.com"));
+        assert!(!domain_matches("*.example.com", "fake-example.com"));
+        assert!(!domain_matches("*.example.com", "example.com"));
+    }
+
+    #[test]
+    fn exact_pattern_matches_case_insensitively() {
+        assert!(domain_matches("Example.com", "example.com"));
+        assert!(!domain_matches("example.com", "example.org"));
+    }
+
+    #[test]
+    fn empty_allowlist_allows_any_host() {
+        let options = ReaderOptions::new();
+        assert!(options.check("http://example.com/file").is_ok());
+    }
+
+    #[test]
+    fn allowlist_blocks_hosts_not_on_the_list() {
+        let options = ReaderOptions::new().allow("*.example.com");
+        assert!(options.check("http://api.example.com/file").is_ok());
+        assert!(options.check("http://evilexample.com/file").is_err());
+    }
+
+    #[test]
+    fn denylist_takes_precedence_over_allowlist() {
+        let options = ReaderOptions::new()
+            .allow("example.com")
+            .deny("example.com");
+        assert!(options.check("http://example.com/file").is_err());
+    }
+
+    #[test]
+    fn reject_private_ips_blocks_loopback_literal() {
+        let options = ReaderOptions::new().reject_private_ips(true);
+        assert!(options.check("http://127.0.0.1/file").is_err());
+        assert!(options.check("http://93.184.216.34/file").is_ok());
+    }
+}
@@ -0,0 +1,191 @@
+//! Parsing for `file://` and `data:` URIs, plus a registry that lets
+//! downstream crates plug in handlers for custom schemes (e.g. `s3://`).
+
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind, Read, Result},
+    path::PathBuf,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use base64::Engine;
+
+/// Opens a [`SourceReader::Custom`](crate::SourceReader::Custom) URI as a reader
+///
+/// Implementations are registered against a scheme prefix (e.g. `"s3://"`)
+/// with [`SourceReader::register_scheme`](crate::SourceReader::register_scheme).
+pub trait SchemeHandler: Send + Sync {
+    /// Opens `uri` (the full URI, including the scheme) for reading
+    fn open(&self, uri: &str) -> Result<Box<dyn Read>>;
+}
+
+fn registry() -> &'static RwLock<HashMap<String, Arc<dyn SchemeHandler>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Arc<dyn SchemeHandler>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers a handler for URIs starting with `prefix` (e.g. `"s3://"`)
+pub(crate) fn register(prefix: &str, handler: impl SchemeHandler + 'static) {
+    registry()
+        .write()
+        .unwrap()
+        .insert(prefix.to_string(), Arc::new(handler));
+}
+
+/// Returns the handler registered for a scheme prefix matching `uri`, if any
+pub(crate) fn lookup(uri: &str) -> Option<Arc<dyn SchemeHandler>> {
+    let registry = registry().read().unwrap();
+    registry
+        .iter()
+        .find(|(prefix, _)| uri.starts_with(prefix.as_str()))
+        .map(|(_, handler)| Arc::clone(handler))
+}
+
+/// Returns `true` if any registered scheme prefix matches `uri`
+pub(crate) fn is_registered(uri: &str) -> bool {
+    lookup(uri).is_some()
+}
+
+/// Opens `uri` using its registered handler
+pub(crate) fn open(uri: &str) -> Result<Box<dyn Read>> {
+    lookup(uri)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::Unsupported,
+                format!("no handler registered for {uri}"),
+            )
+        })?
+        .open(uri)
+}
+
+/// Parses a `file://` URL into a local path, handling Windows drive letters,
+/// backslashes, and percent-encoded path segments
+///
+/// `file:///home/user/file.txt` -> `/home/user/file.txt`
+/// `file:///C:/Users/user/file.txt` -> `C:\Users\user\file.txt`
+/// `file:///tmp/my%20file.txt` -> `/tmp/my file.txt`
+///
+/// Returns `None` if the URL has a non-empty authority other than
+/// `localhost` (e.g. `file://example.com/path`), since that isn't a path on
+/// the local machine.
+pub(crate) fn parse_file_url(url: &str) -> Option<PathBuf> {
+    let rest = url.strip_prefix("file://")?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, ""),
+    };
+
+    if !authority.is_empty() && !authority.eq_ignore_ascii_case("localhost") {
+        return None;
+    }
+
+    let decoded = percent_decode(path).ok()?;
+    let path = String::from_utf8(decoded).ok()?;
+    let path = path.strip_prefix('/').unwrap_or(&path);
+
+    // A leading `<drive>:/...` after stripping the slash is a Windows path
+    // that got an extra `/` prefix from the URL's authority-less form.
+    if path.len() >= 2 && path.as_bytes()[1] == b':' {
+        Some(PathBuf::from(path.replace('/', "\\")))
+    } else {
+        Some(PathBuf::from(format!("/{path}")))
+    }
+}
+
+/// Decodes an RFC 2397 `data:` URI into its raw bytes
+///
+/// Supports both `;base64` payloads and percent-encoded (or literal) text
+/// payloads, e.g. `data:text/plain;base64,SGVsbG8h` or `data:,Hello!`.
+pub(crate) fn parse_data_uri(uri: &str) -> Result<Vec<u8>> {
+    let rest = uri
+        .strip_prefix("data:")
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "not a data: URI"))?;
+
+    let (meta, payload) = rest
+        .split_once(',')
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "data: URI is missing a comma"))?;
+
+    if meta.ends_with(";base64") {
+        base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    } else {
+        percent_decode(payload)
+    }
+}
+
+fn percent_decode(input: &str) -> Result<Vec<u8>> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+            match hex {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 3;
+                }
+                None => {
+                    out.push(bytes[i]);
+                    i += 1;
+                }
+            }
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_file_url_decodes_percent_encoded_paths() {
+        let path = parse_file_url("file:///tmp/my%20file.txt").unwrap();
+        assert_eq!(path, PathBuf::from("/tmp/my file.txt"));
+    }
+
+    #[test]
+    fn parse_file_url_accepts_empty_or_localhost_authority() {
+        assert_eq!(
+            parse_file_url("file:///home/user/file.txt").unwrap(),
+            PathBuf::from("/home/user/file.txt")
+        );
+        assert_eq!(
+            parse_file_url("file://localhost/home/user/file.txt").unwrap(),
+            PathBuf::from("/home/user/file.txt")
+        );
+    }
+
+    #[test]
+    fn parse_file_url_rejects_other_hosts() {
+        assert!(parse_file_url("file://example.com/etc/passwd").is_none());
+    }
+
+    #[test]
+    fn parse_file_url_handles_windows_drive_paths() {
+        let path = parse_file_url("file:///C:/Users/user/file.txt").unwrap();
+        assert_eq!(path, PathBuf::from("C:\\Users\\user\\file.txt"));
+    }
+
+    #[test]
+    fn parse_data_uri_decodes_base64_payload() {
+        let bytes = parse_data_uri("data:text/plain;base64,SGVsbG8h").unwrap();
+        assert_eq!(bytes, b"Hello!");
+    }
+
+    #[test]
+    fn parse_data_uri_decodes_percent_encoded_payload() {
+        let bytes = parse_data_uri("data:,Hello%2C%20world!").unwrap();
+        assert_eq!(bytes, b"Hello, world!");
+    }
+}
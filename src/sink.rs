@@ -0,0 +1,288 @@
+//! Write-side mirror of [`SourceReader`], so a pipeline can accept `-`, a
+//! path, or a URL uniformly on both its input and output ends.
+
+use std::{
+    fs::File,
+    io::{self, Error, Read, Result, Write},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+};
+
+use ureq::{Agent, SendBody};
+
+/// Represents a destination that can be written to: a local file, a remote
+/// URL, or standard output
+#[derive(Debug, Clone)]
+pub enum SourceSink {
+    /// A local file path, created (or truncated) on write
+    Local(PathBuf),
+    /// A remote URL, written via an HTTP PUT
+    Remote(String),
+    /// Standard output (stdout)
+    Stdout,
+}
+
+impl From<&str> for SourceSink {
+    /// Parses a string to the `SourceSink` enum
+    ///
+    /// - **Stdout:** If the string is `-`
+    /// - **Remote:** If the string begins with `http://` or `https://`
+    /// - **Local Path:** If the string didn't match with Stdout or Remote
+    fn from(path: &str) -> Self {
+        if path == "-" {
+            SourceSink::Stdout
+        } else if path.starts_with("http://") || path.starts_with("https://") {
+            SourceSink::Remote(path.to_string())
+        } else {
+            SourceSink::Local(PathBuf::from(path))
+        }
+    }
+}
+
+/// Parses a string to the `SourceSink` enum
+///
+/// - **Stdout:** If the string is `-`
+/// - **Remote:** If the string begins with `http://` or `https://`
+/// - **Local Path:** If the string didn't match with Stdout or Remote
+impl From<String> for SourceSink {
+    fn from(path: String) -> Self {
+        SourceSink::from(path.as_str())
+    }
+}
+
+/// Returns a `SourceSink::Local` for the path
+impl From<PathBuf> for SourceSink {
+    fn from(path: PathBuf) -> Self {
+        SourceSink::Local(path)
+    }
+}
+
+/// Returns a `SourceSink::Local` for the path
+impl From<&Path> for SourceSink {
+    fn from(path: &Path) -> Self {
+        SourceSink::Local(path.to_path_buf())
+    }
+}
+
+impl SourceSink {
+    fn default_agent() -> Agent {
+        Agent::config_builder()
+            .user_agent("source-reader (ureq)")
+            .build()
+            .into()
+    }
+
+    /// Creates a writer for the destination
+    ///
+    /// Call [`FinishWrite::finish`] once all bytes have been written to
+    /// observe whether the destination accepted them; for `Remote`, this is
+    /// the only way to learn whether the HTTP request succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - An optional ureq agent to use if the destination is remote
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use source_reader::{FinishWrite, SourceSink};
+    /// use std::io::Write;
+    /// let sink = SourceSink::from("/path/to/file");
+    /// let mut writer = sink.writer(None).unwrap();
+    /// writer.write_all(b"Hello!").unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    #[cfg(not(doctest))]
+    pub fn writer(&self, agent: Option<Agent>) -> Result<Box<dyn FinishWrite>> {
+        match self {
+            SourceSink::Local(path) => {
+                let file = File::create(path)?;
+                Ok(Box::new(file))
+            }
+            SourceSink::Remote(url) => Ok(Box::new(RemoteWriter::spawn(
+                agent.unwrap_or_else(Self::default_agent),
+                url.clone(),
+            ))),
+            SourceSink::Stdout => Ok(Box::new(io::stdout())),
+        }
+    }
+}
+
+/// A [`Write`] destination that can be finalized to observe whatever result
+/// the destination reported, once all bytes have been written
+///
+/// Plain [`Write`] alone can't surface this for `Remote`: every `write()`
+/// call only hands bytes off to the in-flight request, so the outcome of
+/// that request (did the server accept it?) is only known once the body is
+/// complete.
+pub trait FinishWrite: Write {
+    /// Finalizes the write, returning the destination's result
+    fn finish(self: Box<Self>) -> Result<()>;
+}
+
+impl FinishWrite for File {
+    fn finish(mut self: Box<Self>) -> Result<()> {
+        self.flush()
+    }
+}
+
+impl FinishWrite for io::Stdout {
+    fn finish(mut self: Box<Self>) -> Result<()> {
+        self.flush()
+    }
+}
+
+/// A [`Write`] implementation that streams written bytes to a remote URL
+/// over an HTTP PUT, one chunk at a time, without buffering the whole body
+/// in memory
+///
+/// Writes are handed off to a background thread that feeds them to the
+/// request body as they arrive; the request only completes once
+/// [`FinishWrite::finish`] is called (or the writer is dropped, in which
+/// case any error is discarded).
+struct RemoteWriter {
+    chunks: Option<mpsc::SyncSender<Vec<u8>>>,
+    request: Option<thread::JoinHandle<Result<()>>>,
+}
+
+/// How many written chunks may wait for the request body before `write()`
+/// blocks
+const CHUNK_QUEUE_LEN: usize = 16;
+
+impl RemoteWriter {
+    fn spawn(agent: Agent, url: String) -> Self {
+        let (chunks, rx) = mpsc::sync_channel::<Vec<u8>>(CHUNK_QUEUE_LEN);
+
+        let request = thread::spawn(move || {
+            agent
+                .put(&url)
+                .send(SendBody::from_reader(&mut ChunkReader::new(rx)))
+                .map(|_| ())
+                .map_err(Error::other)
+        });
+
+        RemoteWriter {
+            chunks: Some(chunks),
+            request: Some(request),
+        }
+    }
+}
+
+impl Write for RemoteWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        // An empty chunk would make `ChunkReader::read` return `Ok(0)`,
+        // which signals EOF to the in-flight request body and would end the
+        // upload even though the caller may still write more afterward.
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        match &self.chunks {
+            Some(chunks) => chunks
+                .send(buf.to_vec())
+                .map(|_| buf.len())
+                .map_err(|err| Error::other(err.to_string())),
+            None => Err(Error::other("writer already closed")),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl FinishWrite for RemoteWriter {
+    fn finish(mut self: Box<Self>) -> Result<()> {
+        // Dropping the sender closes the channel, which signals EOF to the
+        // `ChunkReader` on the other end so the PUT request can complete.
+        self.chunks.take();
+
+        match self.request.take() {
+            Some(request) => request
+                .join()
+                .unwrap_or_else(|_| Err(Error::other("remote writer thread panicked"))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RemoteWriter {
+    fn drop(&mut self) {
+        // Best-effort cleanup if `finish()` was never called: the result of
+        // the request is discarded. Call `finish()` to observe it instead.
+        self.chunks.take();
+
+        if let Some(request) = self.request.take() {
+            let _ = request.join();
+        }
+    }
+}
+
+/// Adapts the receiving end of an `mpsc` channel of byte chunks into a [`Read`]
+struct ChunkReader {
+    rx: mpsc::Receiver<Vec<u8>>,
+    pending: Vec<u8>,
+}
+
+impl ChunkReader {
+    fn new(rx: mpsc::Receiver<Vec<u8>>) -> Self {
+        ChunkReader {
+            rx,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.pending.is_empty() {
+            match self.rx.recv() {
+                Ok(chunk) => self.pending = chunk,
+                Err(_) => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.pending.len());
+        buf[..len].copy_from_slice(&self.pending[..len]);
+        self.pending.drain(..len);
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_writer_skips_empty_writes() {
+        let (chunks, rx) = mpsc::sync_channel::<Vec<u8>>(CHUNK_QUEUE_LEN);
+        let mut writer = RemoteWriter {
+            chunks: Some(chunks),
+            request: None,
+        };
+
+        assert_eq!(writer.write(b"").unwrap(), 0);
+        writer.write_all(b"hello").unwrap();
+        drop(writer);
+
+        let received: Vec<Vec<u8>> = rx.try_iter().collect();
+        assert_eq!(received, vec![b"hello".to_vec()]);
+    }
+
+    #[test]
+    fn chunk_reader_reads_chunks_until_the_sender_is_dropped() {
+        let (tx, rx) = mpsc::channel::<Vec<u8>>();
+        let mut reader = ChunkReader::new(rx);
+
+        tx.send(b"hello".to_vec()).unwrap();
+        drop(tx);
+
+        let mut buf = [0u8; 16];
+        let read = reader.read(&mut buf).unwrap();
+        assert_eq!(&buf[..read], b"hello");
+
+        let read = reader.read(&mut buf).unwrap();
+        assert_eq!(read, 0);
+    }
+}